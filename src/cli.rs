@@ -1,31 +1,14 @@
-use clap::{Arg, ArgAction, Command, value_parser};
+use crate::networks::{self, NetworkRegistry};
+use clap::{Arg, ArgAction, Command};
 use colored::*;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum Network {
-    TestNet,
-    MainnetAlpha,
-}
-
-impl std::fmt::Display for Network {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Network::TestNet => write!(f, "testnet"),
-            Network::MainnetAlpha => write!(f, "mainnet-alpha"),
-        }
-    }
-}
-
-impl std::str::FromStr for Network {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "testnet" => Ok(Network::TestNet),
-            "mainnet-alpha" => Ok(Network::MainnetAlpha),
-            _ => Err(format!("Unknown network: {}", s)),
-        }
-    }
+// Which journal entry `--restore` should roll back to: the most recent one
+// by default, or a specific one picked out by the timestamp `--list-backups`
+// prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreTarget {
+    Latest,
+    Timestamp(u64),
 }
 
 #[derive(Debug)]
@@ -33,20 +16,12 @@ pub struct Config {
     pub path: String,
     pub dry_run: bool,
     pub verbose: bool,
-    pub restore: bool,
-    pub network: Option<Network>,
+    pub restore: Option<RestoreTarget>,
+    pub network: Vec<String>,
     pub list_networks: bool,
-}
-
-pub fn print_networks_info() {
-    println!("{}", "Available Sonic Networks:".cyan().bold());
-    println!("\n{}", "Testnet".yellow());
-    println!("RPC URL: {}", "https://api.testnet.sonic.game".bright_green());
-    println!("Usage: {} sonic-migrate --network testnet", "Example:".italic());
-
-    println!("\n{}", "Mainnet Alpha".yellow());
-    println!("RPC URL: {}", "https://api.mainnet-alpha.sonic.game".bright_green());
-    println!("Usage: {} sonic-migrate --network mainnet-alpha", "Example:".italic());
+    pub check_rpc: bool,
+    pub networks: NetworkRegistry,
+    pub list_backups: bool,
 }
 
 impl Config {
@@ -76,15 +51,22 @@ impl Config {
             .arg(
                 Arg::new("restore")
                     .long("restore")
-                    .help("Restore from backup")
+                    .help("Restore a migration from the backup journal (see --list-backups); defaults to the most recent one")
+                    .value_name("TIMESTAMP")
+                    .num_args(0..=1)
+                    .default_missing_value("latest"),
+            )
+            .arg(
+                Arg::new("list-backups")
+                    .long("list-backups")
+                    .help("List the migration backup history")
                     .action(ArgAction::SetTrue),
             )
             .arg(
                 Arg::new("network")
                     .long("network")
                     .short('n')
-                    .help("Target Sonic network (testnet, mainnet-alpha)")
-                    .value_parser(value_parser!(Network)),
+                    .help("Target Sonic network(s), comma-separated (see --networks for the full list)"),
             )
             .arg(
                 Arg::new("networks")
@@ -92,21 +74,83 @@ impl Config {
                     .help("List available networks and their RPC URLs")
                     .action(ArgAction::SetTrue),
             )
+            .arg(
+                Arg::new("check-rpc")
+                    .long("check-rpc")
+                    .help("Verify the target Sonic RPC endpoint is reachable before migrating")
+                    .action(ArgAction::SetTrue),
+            )
             .get_matches();
 
+        let path = matches.get_one::<String>("path").unwrap().to_string();
+        let registry = networks::load_registry(&path);
+
         let list_networks = matches.get_flag("networks");
         if list_networks {
-            print_networks_info();
+            networks::print_networks_info(&registry);
             std::process::exit(0);
         }
 
+        let network = match matches.get_one::<String>("network") {
+            Some(raw) => raw
+                .split(',')
+                .map(|n| n.trim().to_string())
+                .filter(|n| !n.is_empty())
+                .collect::<Vec<_>>(),
+            None => vec!["testnet".to_string()],
+        };
+
+        if network.is_empty() {
+            eprintln!(
+                "{}",
+                "Unknown network: (empty). Run --networks to see the registered networks.".red()
+            );
+            std::process::exit(1);
+        }
+
+        for name in &network {
+            if !registry.contains(name) {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Unknown network: {}. Run --networks to see the registered networks.",
+                        name
+                    )
+                    .red()
+                );
+                std::process::exit(1);
+            }
+        }
+
+        let restore = match matches.get_one::<String>("restore") {
+            None => None,
+            Some(raw) if raw == "latest" => Some(RestoreTarget::Latest),
+            Some(raw) => match raw.parse::<u64>() {
+                Ok(timestamp) => Some(RestoreTarget::Timestamp(timestamp)),
+                Err(_) => {
+                    eprintln!(
+                        "{}",
+                        format!(
+                            "Invalid --restore timestamp: {}. Run --list-backups to see valid timestamps.",
+                            raw
+                        )
+                        .red()
+                    );
+                    std::process::exit(1);
+                }
+            },
+        };
+
         Config {
-            path: matches.get_one::<String>("path").unwrap().to_string(),
+            path,
             dry_run: matches.get_flag("dry-run"),
             verbose: matches.get_flag("verbose"),
-            restore: matches.get_flag("restore"),
-            network: matches.get_one::<Network>("network").cloned(),
+            restore,
+            network,
             list_networks,
+            check_rpc: matches.get_flag("check-rpc"),
+            networks: registry,
+            list_backups: matches.get_flag("list-backups"),
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,118 @@
+use colored::*;
+
+#[derive(Debug, PartialEq)]
+enum DiffLine<'a> {
+    Context(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+// Longest common subsequence table between two line vectors, used to find a
+// minimal set of additions/removals for the diff below.
+fn lcs_table(old: &[&str], new: &[&str]) -> Vec<Vec<usize>> {
+    let (n, m) = (old.len(), new.len());
+    let mut table = vec![vec![0; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if old[i] == new[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+    table
+}
+
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let table = lcs_table(&old_lines, &new_lines);
+
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old_lines.len() && j < new_lines.len() {
+        if old_lines[i] == new_lines[j] {
+            result.push(DiffLine::Context(old_lines[i]));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            result.push(DiffLine::Removed(old_lines[i]));
+            i += 1;
+        } else {
+            result.push(DiffLine::Added(new_lines[j]));
+            j += 1;
+        }
+    }
+    while i < old_lines.len() {
+        result.push(DiffLine::Removed(old_lines[i]));
+        i += 1;
+    }
+    while j < new_lines.len() {
+        result.push(DiffLine::Added(new_lines[j]));
+        j += 1;
+    }
+    result
+}
+
+// Print a unified diff between `old` and `new`: removed lines in red with a
+// `-` prefix, added lines in green with a `+` prefix, and unchanged lines as
+// plain context.
+pub fn print_unified_diff(old: &str, new: &str) {
+    for line in diff_lines(old, new) {
+        match line {
+            DiffLine::Context(l) => println!("  {}", l),
+            DiffLine::Removed(l) => println!("{}", format!("- {}", l).red()),
+            DiffLine::Added(l) => println!("{}", format!("+ {}", l).green()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines_identical_input_is_all_context() {
+        let text = "a\nb\nc";
+        let lines = diff_lines(text, text);
+        assert_eq!(
+            lines,
+            vec![
+                DiffLine::Context("a"),
+                DiffLine::Context("b"),
+                DiffLine::Context("c"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_mix_of_add_remove_and_context() {
+        let old = "a\nb\nc";
+        let new = "a\nx\nc\nd";
+        let lines = diff_lines(old, new);
+
+        assert_eq!(
+            lines,
+            vec![
+                DiffLine::Context("a"),
+                DiffLine::Removed("b"),
+                DiffLine::Added("x"),
+                DiffLine::Context("c"),
+                DiffLine::Added("d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines_empty_old_is_all_additions() {
+        let lines = diff_lines("", "a\nb");
+        assert_eq!(lines, vec![DiffLine::Added("a"), DiffLine::Added("b")]);
+    }
+
+    #[test]
+    fn test_diff_lines_empty_new_is_all_removals() {
+        let lines = diff_lines("a\nb", "");
+        assert_eq!(lines, vec![DiffLine::Removed("a"), DiffLine::Removed("b")]);
+    }
+}
@@ -22,4 +22,22 @@ pub enum MigrationError {
 
     #[error("The specified path is not a valid Anchor project: {0}")]
     NotAnAnchorProject(String),
+
+    #[error("Sonic RPC endpoint is unreachable or unhealthy: {0}")]
+    RpcUnreachable(String),
+
+    #[error("Failed to read manifest: {0}")]
+    ManifestReadFailed(String),
+
+    #[error("Failed to parse manifest: {0}")]
+    ManifestParseError(String),
+
+    #[error("Failed to write manifest: {0}")]
+    ManifestWriteFailed(String),
+
+    #[error("Expected [{0}] to be a table in Anchor.toml")]
+    InvalidTomlSection(String),
+
+    #[error("Backup file {0} no longer matches the hash recorded when it was taken; refusing to restore")]
+    BackupCorrupted(String),
 }
\ No newline at end of file
@@ -0,0 +1,277 @@
+use crate::errors::MigrationError;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// A single migration transaction: when it ran, which network it targeted,
+// and the files it backed up before rewriting (with their pre-migration
+// hashes, so a restore can be sanity-checked against what's on disk).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: u64,
+    pub network: String,
+    pub files: Vec<String>,
+    pub hashes: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct JournalFile {
+    #[serde(default)]
+    entries: Vec<JournalEntry>,
+}
+
+// Models each migration as a reversible transaction: every file a migration
+// touches is copied into `.sonic-migrate/backups/<timestamp>/` and recorded
+// in `.sonic-migrate/journal.toml` before it's rewritten, so any past
+// migration (not just the last one) can be inspected or rolled back.
+pub struct Journal {
+    root: PathBuf,
+}
+
+pub fn now_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the Unix epoch")
+        .as_secs()
+}
+
+fn hash_file(path: &Path) -> Result<String, MigrationError> {
+    let content = std::fs::read(path).map_err(|e| MigrationError::BackupFailed(e.to_string()))?;
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    Ok(format!("{:016x}", hasher.finish()))
+}
+
+impl Journal {
+    pub fn open(project_path: &str) -> Self {
+        Journal {
+            root: Path::new(project_path).join(".sonic-migrate"),
+        }
+    }
+
+    fn journal_path(&self) -> PathBuf {
+        self.root.join("journal.toml")
+    }
+
+    fn backup_dir(&self, timestamp: u64) -> PathBuf {
+        self.root.join("backups").join(timestamp.to_string())
+    }
+
+    fn load(&self) -> JournalFile {
+        std::fs::read_to_string(self.journal_path())
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, file: &JournalFile) -> Result<(), MigrationError> {
+        std::fs::create_dir_all(&self.root)
+            .map_err(|e| MigrationError::BackupFailed(e.to_string()))?;
+        let content = toml::to_string_pretty(file)
+            .map_err(|e| MigrationError::BackupFailed(e.to_string()))?;
+        std::fs::write(self.journal_path(), content)
+            .map_err(|e| MigrationError::BackupFailed(e.to_string()))
+    }
+
+    // Copy `files` into a fresh timestamped backup directory and append a
+    // record of the transaction to the journal. `files` must exist and live
+    // directly under the project root.
+    pub fn record_backup(
+        &self,
+        timestamp: u64,
+        network: &str,
+        files: &[PathBuf],
+    ) -> Result<(), MigrationError> {
+        let backup_dir = self.backup_dir(timestamp);
+        std::fs::create_dir_all(&backup_dir)
+            .map_err(|e| MigrationError::BackupFailed(e.to_string()))?;
+
+        let mut names = Vec::with_capacity(files.len());
+        let mut hashes = Vec::with_capacity(files.len());
+        for file in files {
+            let file_name = file
+                .file_name()
+                .expect("backed up files have a file name")
+                .to_string_lossy()
+                .to_string();
+            hashes.push(hash_file(file)?);
+            std::fs::copy(file, backup_dir.join(&file_name))
+                .map_err(|e| MigrationError::BackupFailed(e.to_string()))?;
+            names.push(file_name);
+        }
+
+        let mut journal = self.load();
+        journal.entries.push(JournalEntry {
+            timestamp,
+            network: network.to_string(),
+            files: names,
+            hashes,
+        });
+        self.save(&journal)
+    }
+
+    pub fn latest_entry(&self) -> Option<JournalEntry> {
+        self.load().entries.into_iter().max_by_key(|e| e.timestamp)
+    }
+
+    // Look up the entry recorded at an exact timestamp, e.g. one printed by
+    // `print_backup_history`.
+    pub fn entry_at(&self, timestamp: u64) -> Option<JournalEntry> {
+        self.load()
+            .entries
+            .into_iter()
+            .find(|e| e.timestamp == timestamp)
+    }
+
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        let mut entries = self.load().entries;
+        entries.sort_by_key(|e| e.timestamp);
+        entries
+    }
+
+    // Restore every file in `entry` back into `project_path`. We verify all
+    // backed-up files are present and still match the hash recorded when
+    // they were backed up before copying any of them back, so a
+    // missing/corrupt backup directory fails the whole restore rather than
+    // leaving the project half-restored.
+    pub fn restore(&self, project_path: &str, entry: &JournalEntry) -> Result<(), MigrationError> {
+        let backup_dir = self.backup_dir(entry.timestamp);
+
+        for (file, expected_hash) in entry.files.iter().zip(&entry.hashes) {
+            let backed_up = backup_dir.join(file);
+            if !backed_up.exists() {
+                return Err(MigrationError::BackupNotFound(
+                    backed_up.to_string_lossy().into_owned(),
+                ));
+            }
+            if &hash_file(&backed_up)? != expected_hash {
+                return Err(MigrationError::BackupCorrupted(
+                    backed_up.to_string_lossy().into_owned(),
+                ));
+            }
+        }
+
+        for file in &entry.files {
+            let target = Path::new(project_path).join(file);
+            std::fs::copy(backup_dir.join(file), &target)
+                .map_err(|e| MigrationError::RestoreFailed(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_file(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_record_and_restore_round_trip() {
+        let project_dir = TempDir::new().unwrap();
+        let file_path = write_file(project_dir.path(), "Anchor.toml", "original content");
+
+        let journal = Journal::open(project_dir.path().to_str().unwrap());
+        journal
+            .record_backup(1, "testnet", &[file_path.clone()])
+            .unwrap();
+
+        std::fs::write(&file_path, "migrated content").unwrap();
+
+        let entry = journal.latest_entry().expect("entry was just recorded");
+        assert_eq!(entry.timestamp, 1);
+        assert_eq!(entry.network, "testnet");
+
+        journal
+            .restore(project_dir.path().to_str().unwrap(), &entry)
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "original content");
+    }
+
+    #[test]
+    fn test_entries_tracks_every_transaction() {
+        let project_dir = TempDir::new().unwrap();
+        let file_path = write_file(project_dir.path(), "Anchor.toml", "v1");
+
+        let journal = Journal::open(project_dir.path().to_str().unwrap());
+        journal
+            .record_backup(1, "testnet", &[file_path.clone()])
+            .unwrap();
+        journal
+            .record_backup(2, "mainnet-alpha", &[file_path.clone()])
+            .unwrap();
+
+        let entries = journal.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].timestamp, 1);
+        assert_eq!(entries[1].timestamp, 2);
+        assert_eq!(journal.entry_at(2).unwrap().network, "mainnet-alpha");
+        assert!(journal.entry_at(999).is_none());
+    }
+
+    #[test]
+    fn test_restore_fails_atomically_when_a_backup_file_is_missing() {
+        let project_dir = TempDir::new().unwrap();
+        let file_a = write_file(project_dir.path(), "Anchor.toml", "a-original");
+        let file_b = write_file(project_dir.path(), "Cargo.toml", "b-original");
+
+        let journal = Journal::open(project_dir.path().to_str().unwrap());
+        journal
+            .record_backup(1, "testnet", &[file_a.clone(), file_b.clone()])
+            .unwrap();
+
+        // Corrupt the backup directory by deleting one of the two backed-up
+        // files, simulating a partially missing backup.
+        let backup_dir = journal.backup_dir(1);
+        std::fs::remove_file(backup_dir.join("Cargo.toml")).unwrap();
+
+        std::fs::write(&file_a, "a-changed").unwrap();
+        std::fs::write(&file_b, "b-changed").unwrap();
+
+        let entry = journal.latest_entry().unwrap();
+        let result = journal.restore(project_dir.path().to_str().unwrap(), &entry);
+
+        assert!(matches!(result, Err(MigrationError::BackupNotFound(_))));
+        // Neither file was touched, since the missing backup is caught
+        // before any copy happens.
+        assert_eq!(std::fs::read_to_string(&file_a).unwrap(), "a-changed");
+        assert_eq!(std::fs::read_to_string(&file_b).unwrap(), "b-changed");
+    }
+
+    #[test]
+    fn test_restore_fails_atomically_when_a_backup_file_is_corrupted() {
+        let project_dir = TempDir::new().unwrap();
+        let file_a = write_file(project_dir.path(), "Anchor.toml", "a-original");
+        let file_b = write_file(project_dir.path(), "Cargo.toml", "b-original");
+
+        let journal = Journal::open(project_dir.path().to_str().unwrap());
+        journal
+            .record_backup(1, "testnet", &[file_a.clone(), file_b.clone()])
+            .unwrap();
+
+        // Corrupt one backed-up file in place, without touching the journal's
+        // recorded hash for it.
+        let backup_dir = journal.backup_dir(1);
+        std::fs::write(backup_dir.join("Cargo.toml"), "tampered").unwrap();
+
+        std::fs::write(&file_a, "a-changed").unwrap();
+        std::fs::write(&file_b, "b-changed").unwrap();
+
+        let entry = journal.latest_entry().unwrap();
+        let result = journal.restore(project_dir.path().to_str().unwrap(), &entry);
+
+        assert!(matches!(result, Err(MigrationError::BackupCorrupted(_))));
+        // Neither file was touched, since the hash mismatch is caught before
+        // any copy happens.
+        assert_eq!(std::fs::read_to_string(&file_a).unwrap(), "a-changed");
+        assert_eq!(std::fs::read_to_string(&file_b).unwrap(), "b-changed");
+    }
+}
@@ -4,10 +4,13 @@ use std::process::exit;
 use std::time::Duration;
 
 mod cli;
+mod diff;
 mod errors;
+mod journal;
 mod migration;
-use cli::{Config, Network};
-use migration::{restore_backup, run_migration};
+mod networks;
+use cli::Config;
+use migration::{print_backup_history, restore_backup, run_migration};
 
 fn main() {
     let config = Config::new();
@@ -15,13 +18,14 @@ fn main() {
     // The networks command is handled in Config::new()
     // If we got here, it wasn't called or it would have exited
 
+    if config.list_backups {
+        print_backup_history(&config.path);
+        return;
+    }
+
     if config.verbose {
         println!("{}", "Starting sonic-migrate...".cyan());
-        if let Some(network) = config.network {
-            println!("{}", format!("Target network: {}", network).cyan());
-        } else {
-            println!("{}", "Using default network (testnet)".cyan());
-        }
+        println!("{}", format!("Target network(s): {}", config.network.join(", ")).cyan());
     }
 
     let progress = ProgressBar::new_spinner();
@@ -32,10 +36,10 @@ fn main() {
             .tick_chars("/|\\- "),
     );
 
-    if config.restore {
+    if let Some(target) = config.restore {
         progress.set_message("Restoring from backup...");
         progress.enable_steady_tick(Duration::from_millis(100));
-        match restore_backup(&config.path) {
+        match restore_backup(&config.path, target) {
             Ok(_) => {
                 progress.finish_with_message("Backup restored successfully.".green().to_string());
                 println!("{}", "Restore complete.".green());
@@ -49,8 +53,8 @@ fn main() {
         return;
     }
 
-    let network_name = config.network.map_or("testnet".to_string(), |n| n.to_string());
-    progress.set_message(format!("Migrating project to Sonic {}...", network_name));
+    let network_list = config.network.join(", ");
+    progress.set_message(format!("Migrating project to Sonic {}...", network_list));
     progress.enable_steady_tick(Duration::from_millis(100));
 
     match run_migration(&config) {
@@ -60,19 +64,16 @@ fn main() {
             println!("{}", "Next steps:".yellow());
             println!("1. Update your dependencies.");
             println!("2. Test your project.");
-            println!("3. Deploy to Sonic {} Network.", network_name);
-            
+            println!("3. Deploy to Sonic {} Network(s).", network_list);
+
             // Display RPC URL info
             println!("\n{}", "Network Information:".cyan());
-            match config.network {
-                Some(Network::MainnetAlpha) => {
-                    println!("Mainnet Alpha RPC URL: {}", "https://api.mainnet-alpha.sonic.game".bright_green());
-                }
-                _ => {
-                    println!("Testnet RPC URL: {}", "https://api.testnet.sonic.game".bright_green());
+            for name in &config.network {
+                if let Some(def) = config.networks.get(name) {
+                    println!("{} RPC URL: {}", name, def.rpc_url.bright_green());
                 }
             }
-            
+
             // Add migration reminder
             println!("\n{}", "To learn more about additional networks, run:".yellow());
             println!("  sonic-migrate --networks");
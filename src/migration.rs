@@ -1,5 +1,7 @@
-use crate::cli::{Config, Network};
+use crate::cli::{Config, RestoreTarget};
 use crate::errors::MigrationError;
+use crate::journal::{self, Journal};
+use crate::networks::NetworkDef;
 use colored::*;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -25,32 +27,197 @@ struct AnchorToml {
     extra: std::collections::HashMap<String, toml::Value>,
 }
 
-// Get the RPC URL based on the network
+// Resolve every network the user selected against the config's registry, in
+// the order given on the command line. Names are already known to exist in
+// the registry at this point, since `Config::new` rejects unknown
+// `--network` entries before a `Config` is ever constructed.
+
+fn resolve_networks(config: &Config) -> Vec<&NetworkDef> {
+    config
+        .network
+        .iter()
+        .map(|name| {
+            config
+                .networks
+                .get(name)
+                .expect("network name was validated during CLI parsing")
+        })
+        .collect()
+}
+
+// The primary target network: the first one listed on the command line. The
+// cluster URL and toolchain/dependency versions are singular per-project, so
+// when several networks are targeted at once they're pinned to this one,
+// while every target still gets its own `[programs.*]` section.
+
+fn primary_network(config: &Config) -> &NetworkDef {
+    resolve_networks(config)[0]
+}
+
+// Pin a Cargo dependency to `version`, whether it's declared as a bare
+// version string (`anchor-lang = "0.29"`) or an inline table
+// (`anchor-lang = { version = "0.29", features = [...] }`). Returns whether
+// the dependency was present and got pinned.
+fn pin_cargo_dependency(table: &mut toml::value::Table, name: &str, version: &str) -> bool {
+    match table.get_mut(name) {
+        Some(toml::Value::String(v)) => {
+            *v = version.to_string();
+            true
+        }
+        Some(toml::Value::Table(dep)) => {
+            dep.insert("version".to_string(), toml::Value::String(version.to_string()));
+            true
+        }
+        _ => false,
+    }
+}
 
-fn get_network_rpc_url(network: Network) -> &'static str {
-    match network {
-        Network::TestNet => "https://api.testnet.sonic.game",
-        Network::MainnetAlpha => "https://api.mainnet-alpha.sonic.game",
+// Pin the Anchor/Solana toolchain versions in a `[dependencies]`-like table.
+fn pin_toolchain_deps(table: &mut toml::value::Table, network: &NetworkDef, changed: &mut bool) {
+    if let Some(version) = &network.anchor_version {
+        for dep in ["anchor-lang", "anchor-spl"] {
+            *changed |= pin_cargo_dependency(table, dep, version);
+        }
+    }
+    if let Some(version) = &network.solana_version {
+        for dep in ["solana-program", "solana-sdk", "solana-client"] {
+            *changed |= pin_cargo_dependency(table, dep, version);
+        }
     }
 }
 
-// Map the cluster to the corresponding RPC URL
+// Pin the Anchor/Solana toolchain versions used across the workspace's
+// `Cargo.toml`: its own `[dependencies]`/`[dev-dependencies]` and, if this is
+// a workspace root, `[workspace.dependencies]`. Returns the rewritten value
+// if a Cargo.toml was found and something in it actually got pinned; the
+// caller is responsible for backing it up before writing it back. Returns
+// `None` when nothing changed, so a `toml::Value` round-trip (which drops
+// comments and reorders tables) never touches a Cargo.toml sonic-migrate
+// didn't need to modify.
+fn migrate_cargo_toml(
+    config: &Config,
+    network: &NetworkDef,
+) -> Result<Option<(std::path::PathBuf, toml::Value)>, MigrationError> {
+    let cargo_toml_path = Path::new(&config.path).join("Cargo.toml");
+    if !cargo_toml_path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&cargo_toml_path)
+        .map_err(|e| MigrationError::ManifestReadFailed(e.to_string()))?;
+    let mut toml_value: toml::Value = content
+        .parse()
+        .map_err(|e: toml::de::Error| MigrationError::ManifestParseError(e.to_string()))?;
+
+    let mut changed = false;
+    for section in ["dependencies", "dev-dependencies"] {
+        if let Some(table) = toml_value.get_mut(section).and_then(|v| v.as_table_mut()) {
+            pin_toolchain_deps(table, network, &mut changed);
+        }
+    }
+    if let Some(table) = toml_value
+        .get_mut("workspace")
+        .and_then(|w| w.get_mut("dependencies"))
+        .and_then(|v| v.as_table_mut())
+    {
+        pin_toolchain_deps(table, network, &mut changed);
+    }
+
+    if !changed {
+        return Ok(None);
+    }
 
-fn map_cluster_to_soon(cluster: &str, network: Option<Network>) -> String {
-    // Use the specified network or default to testnet
-    let network = network.unwrap_or(Network::TestNet);
-    get_network_rpc_url(network).to_string()
+    if config.verbose {
+        println!("{}", "Pinned Anchor/Solana toolchain versions in Cargo.toml.".cyan());
+    }
+
+    Ok(Some((cargo_toml_path, toml_value)))
 }
 
-// Get the programs section name based on the network
-// This function is used to determine the section name in the TOML file
-// for the programs based on the selected network.
-// For example, if the network is TestNet, it will return "testnet"
+// Pin the JS SDK version in `package.json`'s `dependencies`/
+// `devDependencies`. Returns the rewritten value if a package.json was found
+// and the SDK version actually got pinned; the caller is responsible for
+// backing it up before writing it back. Returns `None` when nothing changed,
+// so package.json is never rewritten unless it actually needed pinning.
+fn migrate_package_json(
+    config: &Config,
+    network: &NetworkDef,
+) -> Result<Option<(std::path::PathBuf, serde_json::Value)>, MigrationError> {
+    let package_json_path = Path::new(&config.path).join("package.json");
+    if !package_json_path.exists() {
+        return Ok(None);
+    }
 
-fn get_programs_section_name(network: Network) -> &'static str {
-    match network {
-        Network::TestNet => "testnet",
-        Network::MainnetAlpha => "mainnet",
+    let content = fs::read_to_string(&package_json_path)
+        .map_err(|e| MigrationError::ManifestReadFailed(e.to_string()))?;
+    let mut json_value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| MigrationError::ManifestParseError(e.to_string()))?;
+
+    let mut changed = false;
+    if let Some(version) = &network.js_sdk_version {
+        for section in ["dependencies", "devDependencies"] {
+            if let Some(deps) = json_value.get_mut(section).and_then(|v| v.as_object_mut()) {
+                if deps.contains_key("@coral-xyz/anchor") {
+                    deps.insert(
+                        "@coral-xyz/anchor".to_string(),
+                        serde_json::Value::String(version.clone()),
+                    );
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    if !changed {
+        return Ok(None);
+    }
+
+    if config.verbose {
+        println!("{}", "Pinned JS SDK version in package.json.".cyan());
+    }
+
+    Ok(Some((package_json_path, json_value)))
+}
+
+// Verify the target Sonic RPC endpoint is reachable and healthy before we
+// touch any files. We only look at the top-level `result`/`error` fields of
+// the JSON-RPC response to avoid pulling in a full JSON-RPC client.
+
+fn check_rpc_health(rpc_url: &str) -> Result<(), MigrationError> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout(std::time::Duration::from_secs(5))
+        .build();
+
+    let response = agent
+        .post(rpc_url)
+        .set("Content-Type", "application/json")
+        .send_string(r#"{"jsonrpc":"2.0","id":1,"method":"getHealth"}"#)
+        .map_err(|e| MigrationError::RpcUnreachable(e.to_string()))?;
+
+    let body = response
+        .into_string()
+        .map_err(|e| MigrationError::RpcUnreachable(e.to_string()))?;
+
+    let parsed: serde_json::Value = serde_json::from_str(&body)
+        .map_err(|e| MigrationError::RpcUnreachable(format!("invalid JSON-RPC response: {}", e)))?;
+
+    if let Some(error) = parsed.get("error") {
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("unknown error");
+        return Err(MigrationError::RpcUnreachable(message.to_string()));
+    }
+
+    match parsed.get("result").and_then(|r| r.as_str()) {
+        Some("ok") => Ok(()),
+        Some(other) => Err(MigrationError::RpcUnreachable(format!(
+            "endpoint reported unhealthy status: {}",
+            other
+        ))),
+        None => Err(MigrationError::RpcUnreachable(
+            "missing result field in response".to_string(),
+        )),
     }
 }
 
@@ -59,17 +226,14 @@ fn get_programs_section_name(network: Network) -> &'static str {
 pub fn run_migration(config: &Config) -> Result<(), MigrationError> {
     validate_anchor_project(&config.path)?;
 
-    let anchor_toml_path = Path::new(&config.path).join("Anchor.toml");
-
-    // Backup original Anchor.toml
-    let backup_path = anchor_toml_path.with_extension("toml.bak");
-    fs::copy(&anchor_toml_path, &backup_path)
-        .map_err(|e| MigrationError::BackupFailed(e.to_string()))?;
-
-    if config.verbose {
-        println!("{}", "Backup created successfully.".cyan());
+    if config.check_rpc {
+        for network in resolve_networks(config) {
+            check_rpc_health(&network.rpc_url)?;
+        }
     }
 
+    let anchor_toml_path = Path::new(&config.path).join("Anchor.toml");
+
     // Read Anchor.toml
     let content = fs::read_to_string(&anchor_toml_path)
         .map_err(|e| MigrationError::ReadFailed(e.to_string()))?;
@@ -88,9 +252,9 @@ pub fn run_migration(config: &Config) -> Result<(), MigrationError> {
                 .map(|c| c.to_string());
             
             if let Some(cluster) = cluster_value {
-                let soon_rpc = map_cluster_to_soon(&cluster, config.network);
-                table.insert("cluster".to_string(), toml::Value::String(soon_rpc.to_string()));
-                
+                let soon_rpc = &primary_network(config).rpc_url;
+                table.insert("cluster".to_string(), toml::Value::String(soon_rpc.clone()));
+
                 if config.verbose {
                     println!("{}", format!("Updating cluster from '{}' to '{}'", cluster, soon_rpc).cyan());
                 }
@@ -98,70 +262,151 @@ pub fn run_migration(config: &Config) -> Result<(), MigrationError> {
         }
     }
 
-    // Get programs section name based on network
-    let programs_section_name = config.network.map_or("testnet", get_programs_section_name);
-
-    // Update programs section: change programs.localnet to programs.testnet or programs.mainnet
+    // Add/overwrite a `[programs.<network>]` section for every target
+    // network, cloned from `[programs.localnet]`, without touching localnet
+    // itself - a project can be configured for several Sonic clusters at once.
     if let Some(programs) = toml_value.get_mut("programs") {
         if let Some(table) = programs.as_table_mut() {
-            if let Some(localnet) = table.remove("localnet") {
-                table.insert(programs_section_name.to_string(), localnet);
-                if config.verbose {
-                    println!("{}", format!("Updated programs.localnet to programs.{}", programs_section_name).cyan());
+            if let Some(localnet) = table.get("localnet").cloned() {
+                for network in resolve_networks(config) {
+                    let section_name = &network.programs_section;
+                    table.insert(section_name.clone(), localnet.clone());
+                    if config.verbose {
+                        println!("{}", format!("Updated programs.{} from programs.localnet", section_name).cyan());
+                    }
                 }
             }
         }
     }
 
+    // Pin the Anchor/Solana toolchain in Anchor.toml's [toolchain] table
+    let network = primary_network(config);
+    if network.anchor_version.is_some() || network.solana_version.is_some() {
+        let toolchain = toml_value
+            .as_table_mut()
+            .unwrap()
+            .entry("toolchain")
+            .or_insert_with(|| toml::Value::Table(toml::value::Table::new()))
+            .as_table_mut()
+            .ok_or_else(|| MigrationError::InvalidTomlSection("toolchain".to_string()))?;
+
+        if let Some(version) = &network.anchor_version {
+            toolchain.insert("anchor_version".to_string(), toml::Value::String(version.clone()));
+        }
+        if let Some(version) = &network.solana_version {
+            toolchain.insert("solana_version".to_string(), toml::Value::String(version.clone()));
+        }
+    }
+
     if config.verbose {
         println!("{}", "Configuration updated successfully.".cyan());
     }
 
-    // Write back to Anchor.toml unless dry_run
-    if !config.dry_run {
+    // Pin dependencies in the workspace Cargo.toml and package.json alongside
+    // the Anchor.toml rewrite above
+    let cargo_migration = migrate_cargo_toml(config, network)?;
+    let package_json_migration = migrate_package_json(config, network)?;
+
+    if config.dry_run {
+        println!("{}", "Dry run enabled. Changes not written.".yellow());
+        println!("{}", "Anchor.toml diff:".cyan());
         let toml_string = toml::to_string_pretty(&toml_value)
             .map_err(|e| MigrationError::TomlParseError(e.to_string()))?;
+        crate::diff::print_unified_diff(&content, &toml_string);
+        return Ok(());
+    }
+
+    // Back up every file this migration is about to rewrite into a fresh
+    // journal entry before touching any of them.
+    let mut touched_files = vec![anchor_toml_path.clone()];
+    if let Some((path, _)) = &cargo_migration {
+        touched_files.push(path.clone());
+    }
+    if let Some((path, _)) = &package_json_migration {
+        touched_files.push(path.clone());
+    }
+
+    let backup_journal = Journal::open(&config.path);
+    let network_name = config.network.join(",");
+    backup_journal.record_backup(journal::now_timestamp(), &network_name, &touched_files)?;
+
+    if config.verbose {
+        println!("{}", "Backup created successfully.".cyan());
+    }
+
+    let toml_string = toml::to_string_pretty(&toml_value)
+        .map_err(|e| MigrationError::TomlParseError(e.to_string()))?;
+    fs::write(&anchor_toml_path, toml_string).map_err(|e| MigrationError::WriteFailed(e.to_string()))?;
+    if config.verbose {
+        println!("{}", "Anchor.toml written successfully.".cyan());
+    }
 
-        fs::write(&anchor_toml_path, toml_string)
-            .map_err(|e| MigrationError::WriteFailed(e.to_string()))?;
+    if let Some((cargo_toml_path, cargo_value)) = cargo_migration {
+        let cargo_string = toml::to_string_pretty(&cargo_value)
+            .map_err(|e| MigrationError::ManifestParseError(e.to_string()))?;
+        fs::write(&cargo_toml_path, cargo_string)
+            .map_err(|e| MigrationError::ManifestWriteFailed(e.to_string()))?;
+        if config.verbose {
+            println!("{}", "Cargo.toml written successfully.".cyan());
+        }
+    }
 
+    if let Some((package_json_path, package_value)) = package_json_migration {
+        let package_string = serde_json::to_string_pretty(&package_value)
+            .map_err(|e| MigrationError::ManifestParseError(e.to_string()))?;
+        fs::write(&package_json_path, package_string)
+            .map_err(|e| MigrationError::ManifestWriteFailed(e.to_string()))?;
         if config.verbose {
-            println!("{}", "Anchor.toml written successfully.".cyan());
+            println!("{}", "package.json written successfully.".cyan());
         }
-    } else {
-        println!("{}", "Dry run enabled. Changes not written.".yellow());
-        println!(
-            "{}",
-            toml::to_string_pretty(&toml_value)
-                .map_err(|e| MigrationError::TomlParseError(e.to_string()))?
-                .cyan()
-        );
     }
 
     Ok(())
 }
 
-// Restore backup
+// Restore a migration from the journal: the most recent one, or a specific
+// one picked out by timestamp (see `RestoreTarget` and `--list-backups`).
+// Every file the migration touched is restored from its backup; see
+// `Journal::restore` for how the all-or-nothing behavior is enforced.
+
+pub fn restore_backup(path: &str, target: RestoreTarget) -> Result<(), MigrationError> {
+    let journal = Journal::open(path);
+    let entry = match target {
+        RestoreTarget::Latest => journal.latest_entry().ok_or_else(|| {
+            MigrationError::BackupNotFound("no migration history found".to_string())
+        })?,
+        RestoreTarget::Timestamp(timestamp) => journal.entry_at(timestamp).ok_or_else(|| {
+            MigrationError::BackupNotFound(format!(
+                "no migration found with timestamp {}",
+                timestamp
+            ))
+        })?,
+    };
+
+    journal.restore(path, &entry)
+}
 
-pub fn restore_backup(path: &str) -> Result<(), MigrationError> {
-    let anchor_toml_path = Path::new(path).join("Anchor.toml");
-    let backup_path = anchor_toml_path.with_extension("toml.bak");
+// Print every migration recorded in the journal, most recent last.
 
-    if !backup_path.exists() {
-        return Err(MigrationError::BackupNotFound(
-            backup_path.to_string_lossy().into_owned(),
-        ));
-    }
+pub fn print_backup_history(path: &str) {
+    let journal = Journal::open(path);
+    let entries = journal.entries();
 
-    fs::copy(&backup_path, &anchor_toml_path)
-        .map_err(|e| MigrationError::RestoreFailed(e.to_string()))?;
-
-    if Path::new(&backup_path).exists() {
-        fs::remove_file(backup_path)
-            .map_err(|e| MigrationError::RestoreFailed(e.to_string()))?;
+    if entries.is_empty() {
+        println!("{}", "No migration history found.".yellow());
+        return;
     }
 
-    Ok(())
+    println!("{}", "Migration history:".cyan().bold());
+    for entry in entries {
+        println!(
+            "\n{} {}",
+            "Timestamp:".yellow(),
+            entry.timestamp.to_string().bright_green()
+        );
+        println!("Network: {}", entry.network);
+        println!("Files: {}", entry.files.join(", "));
+    }
 }
 
 // validate if the project is an Anchor project
@@ -186,7 +431,9 @@ fn validate_anchor_project(path: &str) -> Result<(), MigrationError> {
 mod tests {
     use super::*;
     use std::fs;
-    use std::path::Path;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
     use tempfile::TempDir;
 
     fn create_test_anchor_project() -> TempDir {
@@ -230,9 +477,12 @@ test = "yarn run ts-mocha -p ./tsconfig.json -t 1000000 tests/**/*.ts"
             path: test_dir.path().to_str().unwrap().to_string(),
             dry_run: true,
             verbose: false,
-            restore: false,
-            network: None,
+            restore: None,
+            network: vec!["testnet".to_string()],
             list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry(test_dir.path().to_str().unwrap()),
+            list_backups: false,
         };
 
         let result = run_migration(&config);
@@ -250,9 +500,12 @@ test = "yarn run ts-mocha -p ./tsconfig.json -t 1000000 tests/**/*.ts"
             path: test_dir.path().to_str().unwrap().to_string(),
             dry_run: false,
             verbose: false,
-            restore: false,
-            network: None,
+            restore: None,
+            network: vec!["testnet".to_string()],
             list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry(test_dir.path().to_str().unwrap()),
+            list_backups: false,
         };
 
         let result = run_migration(&config);
@@ -263,8 +516,11 @@ test = "yarn run ts-mocha -p ./tsconfig.json -t 1000000 tests/**/*.ts"
         assert!(content.contains("https://api.testnet.sonic.game"));
         assert!(content.contains("[programs.testnet]"));
 
-        // Verify backup was created
-        assert!(Path::new(&test_dir.path().join("Anchor.toml.bak")).exists());
+        // Verify a journal entry with a backup was created
+        let journal = crate::journal::Journal::open(test_dir.path().to_str().unwrap());
+        let entry = journal.latest_entry().expect("migration should have recorded a backup");
+        assert_eq!(entry.network, "testnet");
+        assert!(entry.files.iter().any(|f| f == "Anchor.toml"));
     }
 
     #[test]
@@ -274,9 +530,12 @@ test = "yarn run ts-mocha -p ./tsconfig.json -t 1000000 tests/**/*.ts"
             path: test_dir.path().to_str().unwrap().to_string(),
             dry_run: false,
             verbose: false,
-            restore: false,
-            network: Some(Network::MainnetAlpha),
+            restore: None,
+            network: vec!["mainnet-alpha".to_string()],
             list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry(test_dir.path().to_str().unwrap()),
+            list_backups: false,
         };
 
         let result = run_migration(&config);
@@ -297,14 +556,18 @@ test = "yarn run ts-mocha -p ./tsconfig.json -t 1000000 tests/**/*.ts"
             path: test_dir.path().to_str().unwrap().to_string(),
             dry_run: false,
             verbose: false,
-            restore: false,
-            network: None,
+            restore: None,
+            network: vec!["testnet".to_string()],
             list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry(test_dir.path().to_str().unwrap()),
+            list_backups: false,
         };
         run_migration(&config).unwrap();
 
         // Then restore
-        let restore_result = restore_backup(test_dir.path().to_str().unwrap());
+        let restore_result =
+            restore_backup(test_dir.path().to_str().unwrap(), RestoreTarget::Latest);
         assert!(restore_result.is_ok());
 
         // Verify content was restored
@@ -312,20 +575,295 @@ test = "yarn run ts-mocha -p ./tsconfig.json -t 1000000 tests/**/*.ts"
         assert!(content.contains("cluster = \"Localnet\""));
     }
 
+    #[test]
+    fn test_restore_backup_by_timestamp() {
+        let test_dir = create_test_anchor_project();
+        let config = Config {
+            path: test_dir.path().to_str().unwrap().to_string(),
+            dry_run: false,
+            verbose: false,
+            restore: None,
+            network: vec!["testnet".to_string()],
+            list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry(test_dir.path().to_str().unwrap()),
+            list_backups: false,
+        };
+        run_migration(&config).unwrap();
+
+        let journal = crate::journal::Journal::open(test_dir.path().to_str().unwrap());
+        let entry = journal.latest_entry().expect("migration should have recorded a backup");
+
+        let restore_result = restore_backup(
+            test_dir.path().to_str().unwrap(),
+            RestoreTarget::Timestamp(entry.timestamp),
+        );
+        assert!(restore_result.is_ok());
+
+        let content = fs::read_to_string(test_dir.path().join("Anchor.toml")).unwrap();
+        assert!(content.contains("cluster = \"Localnet\""));
+
+        // An unknown timestamp is reported instead of silently restoring the
+        // latest entry.
+        let missing_result = restore_backup(
+            test_dir.path().to_str().unwrap(),
+            RestoreTarget::Timestamp(entry.timestamp + 999),
+        );
+        assert!(matches!(
+            missing_result,
+            Err(MigrationError::BackupNotFound(_))
+        ));
+    }
+
     #[test]
     fn test_invalid_path() {
         let config = Config {
             path: "/nonexistent/path".to_string(),
             dry_run: false,
             verbose: false,
-            restore: false,
-            network: None,
+            restore: None,
+            network: vec!["testnet".to_string()],
             list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry("/nonexistent/path"),
+            list_backups: false,
         };
 
         let result = run_migration(&config);
         assert!(matches!(result, Err(MigrationError::NotAnAnchorProject(_))));
     }
+
+    // Serve a single canned JSON-RPC response to the next connection made to
+    // the returned URL, so `check_rpc_health`'s parsing branches can be
+    // exercised without reaching a real Sonic cluster.
+    fn spawn_mock_rpc(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        format!("http://{}", addr)
+    }
+
+    #[test]
+    fn test_check_rpc_health_ok() {
+        let url = spawn_mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"ok"}"#);
+        assert!(check_rpc_health(&url).is_ok());
+    }
+
+    #[test]
+    fn test_check_rpc_health_unhealthy_status() {
+        let url = spawn_mock_rpc(r#"{"jsonrpc":"2.0","id":1,"result":"behind"}"#);
+        let result = check_rpc_health(&url);
+        assert!(matches!(result, Err(MigrationError::RpcUnreachable(_))));
+    }
+
+    #[test]
+    fn test_check_rpc_health_error_field() {
+        let url = spawn_mock_rpc(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"node is unhealthy"}}"#,
+        );
+        let result = check_rpc_health(&url);
+        match result {
+            Err(MigrationError::RpcUnreachable(msg)) => assert_eq!(msg, "node is unhealthy"),
+            other => panic!("expected RpcUnreachable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_check_rpc_health_invalid_json() {
+        let url = spawn_mock_rpc("not json");
+        let result = check_rpc_health(&url);
+        assert!(matches!(result, Err(MigrationError::RpcUnreachable(_))));
+    }
+
+    #[test]
+    fn test_migrate_cargo_toml_pins_dependency_versions() {
+        let test_dir = create_test_anchor_project();
+        fs::write(
+            test_dir.path().join("Cargo.toml"),
+            r#"[package]
+name = "test"
+version = "0.1.0"
+
+[dependencies]
+anchor-lang = "0.29.0"
+solana-program = { version = "1.17.0", features = ["no-entrypoint"] }
+"#,
+        )
+        .unwrap();
+
+        let config = Config {
+            path: test_dir.path().to_str().unwrap().to_string(),
+            dry_run: false,
+            verbose: false,
+            restore: None,
+            network: vec!["testnet".to_string()],
+            list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry(test_dir.path().to_str().unwrap()),
+            list_backups: false,
+        };
+
+        let migration = migrate_cargo_toml(&config, primary_network(&config))
+            .unwrap()
+            .expect("Cargo.toml exists");
+        let cargo_toml = toml::to_string_pretty(&migration.1).unwrap();
+        assert!(cargo_toml.contains("0.30.1"));
+        assert!(cargo_toml.contains("1.18.11"));
+    }
+
+    #[test]
+    fn test_migrate_cargo_toml_skips_unaffected_manifest() {
+        let test_dir = create_test_anchor_project();
+        let original = "[package]\nname = \"test\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1.0\"\n";
+        fs::write(test_dir.path().join("Cargo.toml"), original).unwrap();
+
+        let config = Config {
+            path: test_dir.path().to_str().unwrap().to_string(),
+            dry_run: false,
+            verbose: false,
+            restore: None,
+            network: vec!["testnet".to_string()],
+            list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry(test_dir.path().to_str().unwrap()),
+            list_backups: false,
+        };
+
+        // No anchor-*/solana-* dependency is present to pin, so the manifest
+        // should be left alone instead of being round-tripped through
+        // `toml::Value` (which would strip comments and reorder tables).
+        let migration = migrate_cargo_toml(&config, primary_network(&config)).unwrap();
+        assert!(migration.is_none());
+
+        // run_migration must therefore neither back up nor rewrite Cargo.toml.
+        run_migration(&config).unwrap();
+        let content = fs::read_to_string(test_dir.path().join("Cargo.toml")).unwrap();
+        assert_eq!(content, original);
+        let journal = crate::journal::Journal::open(test_dir.path().to_str().unwrap());
+        let entry = journal.latest_entry().expect("migration should have recorded a backup");
+        assert!(!entry.files.iter().any(|f| f == "Cargo.toml"));
+    }
+
+    #[test]
+    fn test_migrate_package_json_pins_sdk_version() {
+        let test_dir = create_test_anchor_project();
+        fs::write(
+            test_dir.path().join("package.json"),
+            r#"{"dependencies": {"@coral-xyz/anchor": "0.29.0"}}"#,
+        )
+        .unwrap();
+
+        let config = Config {
+            path: test_dir.path().to_str().unwrap().to_string(),
+            dry_run: false,
+            verbose: false,
+            restore: None,
+            network: vec!["testnet".to_string()],
+            list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry(test_dir.path().to_str().unwrap()),
+            list_backups: false,
+        };
+
+        let migration = migrate_package_json(&config, primary_network(&config))
+            .unwrap()
+            .expect("package.json exists");
+        assert_eq!(migration.1["dependencies"]["@coral-xyz/anchor"], "0.4.2");
+    }
+
+    #[test]
+    fn test_migrate_package_json_skips_unaffected_manifest() {
+        let test_dir = create_test_anchor_project();
+        fs::write(
+            test_dir.path().join("package.json"),
+            r#"{"dependencies": {"some-other-package": "1.0.0"}}"#,
+        )
+        .unwrap();
+
+        let config = Config {
+            path: test_dir.path().to_str().unwrap().to_string(),
+            dry_run: false,
+            verbose: false,
+            restore: None,
+            network: vec!["testnet".to_string()],
+            list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry(test_dir.path().to_str().unwrap()),
+            list_backups: false,
+        };
+
+        let migration = migrate_package_json(&config, primary_network(&config)).unwrap();
+        assert!(migration.is_none());
+    }
+
+    #[test]
+    fn test_toolchain_section_not_a_table_returns_error() {
+        let test_dir = TempDir::new().unwrap();
+        fs::write(
+            test_dir.path().join("Anchor.toml"),
+            "toolchain = \"legacy\"\n\n[provider]\ncluster = \"Localnet\"\nwallet = \"~/.config/solana/id.json\"\n\n[programs.localnet]\nmigration = \"EtQdsPNDckBhME3gRjcj9Z4Z9tGEYAoHjWKv7aHJgBua\"\n",
+        )
+        .unwrap();
+        fs::write(
+            test_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"test\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let config = Config {
+            path: test_dir.path().to_str().unwrap().to_string(),
+            dry_run: false,
+            verbose: false,
+            restore: None,
+            network: vec!["testnet".to_string()],
+            list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry(test_dir.path().to_str().unwrap()),
+            list_backups: false,
+        };
+
+        let result = run_migration(&config);
+        assert!(matches!(result, Err(MigrationError::InvalidTomlSection(_))));
+    }
+
+    #[test]
+    fn test_multi_network_preserves_localnet_and_adds_each_programs_section() {
+        let test_dir = create_test_anchor_project();
+        let config = Config {
+            path: test_dir.path().to_str().unwrap().to_string(),
+            dry_run: false,
+            verbose: false,
+            restore: None,
+            network: vec!["testnet".to_string(), "mainnet-alpha".to_string()],
+            list_networks: false,
+            check_rpc: false,
+            networks: crate::networks::load_registry(test_dir.path().to_str().unwrap()),
+            list_backups: false,
+        };
+
+        let result = run_migration(&config);
+        assert!(result.is_ok());
+
+        let content = fs::read_to_string(test_dir.path().join("Anchor.toml")).unwrap();
+        assert!(content.contains("[programs.localnet]"));
+        assert!(content.contains("[programs.testnet]"));
+        assert!(content.contains("[programs.mainnet]"));
+        // The primary network (first in the list) pins the cluster URL.
+        assert!(content.contains("https://api.testnet.sonic.game"));
+    }
 }
 
 
@@ -337,7 +875,7 @@ test = "yarn run ts-mocha -p ./tsconfig.json -t 1000000 tests/**/*.ts"
 //         path: test_dir.path().to_str().unwrap().to_string(),
 //         dry_run: false,
 //         verbose: false,
-//         restore: false,
+//         restore: None,
 //         network: None,
 //         list_networks: false,
 //     };
@@ -354,7 +892,7 @@ test = "yarn run ts-mocha -p ./tsconfig.json -t 1000000 tests/**/*.ts"
 //         path: test_dir.path().to_str().unwrap().to_string(),
 //         dry_run: false,
 //         verbose: false,
-//         restore: false,
+//         restore: None,
 //         network: Some(Network::MainnetAlpha),
 //         list_networks: false,
 //     };
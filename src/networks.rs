@@ -0,0 +1,204 @@
+use colored::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+// A single entry in the network registry: where to reach the cluster, which
+// `[programs.*]` table in Anchor.toml it corresponds to, and the toolchain/
+// SDK versions Sonic requires on that cluster. The version fields are
+// optional so a user-defined network can opt out of dependency pinning
+// entirely by omitting them.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkDef {
+    pub rpc_url: String,
+    pub programs_section: String,
+    #[serde(default)]
+    pub anchor_version: Option<String>,
+    #[serde(default)]
+    pub solana_version: Option<String>,
+    #[serde(default)]
+    pub js_sdk_version: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NetworksFile {
+    #[serde(default)]
+    networks: HashMap<String, NetworkDef>,
+}
+
+// The set of networks sonic-migrate knows how to target: the built-in
+// testnet/mainnet-alpha pair merged with anything defined in a
+// `sonic-networks.toml`. Keeping this as a registry rather than an enum lets
+// users add private or newly launched clusters without recompiling.
+#[derive(Debug, Clone)]
+pub struct NetworkRegistry(HashMap<String, NetworkDef>);
+
+impl NetworkRegistry {
+    pub fn get(&self, name: &str) -> Option<&NetworkDef> {
+        self.0.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.0.keys().map(|s| s.as_str()).collect();
+        names.sort();
+        names
+    }
+}
+
+fn builtin_networks() -> HashMap<String, NetworkDef> {
+    let mut networks = HashMap::new();
+    networks.insert(
+        "testnet".to_string(),
+        NetworkDef {
+            rpc_url: "https://api.testnet.sonic.game".to_string(),
+            programs_section: "testnet".to_string(),
+            anchor_version: Some("0.30.1".to_string()),
+            solana_version: Some("1.18.11".to_string()),
+            js_sdk_version: Some("0.4.2".to_string()),
+        },
+    );
+    networks.insert(
+        "mainnet-alpha".to_string(),
+        NetworkDef {
+            rpc_url: "https://api.mainnet-alpha.sonic.game".to_string(),
+            programs_section: "mainnet".to_string(),
+            anchor_version: Some("0.30.1".to_string()),
+            solana_version: Some("1.18.11".to_string()),
+            js_sdk_version: Some("0.4.2".to_string()),
+        },
+    );
+    networks
+}
+
+// Look for `sonic-networks.toml` in the project directory first, then fall
+// back to the user's config directory, so a project-local registry can
+// override a machine-wide one.
+fn find_networks_file(project_dir: &str) -> Option<PathBuf> {
+    let project_path = Path::new(project_dir).join("sonic-networks.toml");
+    if project_path.exists() {
+        return Some(project_path);
+    }
+
+    let home = std::env::var("HOME").ok()?;
+    let user_path = Path::new(&home)
+        .join(".config")
+        .join("sonic-migrate")
+        .join("sonic-networks.toml");
+    if user_path.exists() {
+        return Some(user_path);
+    }
+
+    None
+}
+
+// Load the built-in networks merged with any user-defined
+// `sonic-networks.toml`, with user entries taking precedence over a built-in
+// of the same name.
+pub fn load_registry(project_dir: &str) -> NetworkRegistry {
+    let mut networks = builtin_networks();
+
+    if let Some(path) = find_networks_file(project_dir) {
+        match std::fs::read_to_string(&path) {
+            Ok(content) => match toml::from_str::<NetworksFile>(&content) {
+                Ok(file) => networks.extend(file.networks),
+                Err(e) => eprintln!(
+                    "{}",
+                    format!("Warning: failed to parse {}: {}", path.display(), e).yellow()
+                ),
+            },
+            Err(e) => eprintln!(
+                "{}",
+                format!("Warning: failed to read {}: {}", path.display(), e).yellow()
+            ),
+        }
+    }
+
+    NetworkRegistry(networks)
+}
+
+pub fn print_networks_info(registry: &NetworkRegistry) {
+    println!("{}", "Available Sonic Networks:".cyan().bold());
+    for name in registry.names() {
+        let def = registry.get(name).expect("name came from the registry");
+        println!("\n{}", name.yellow());
+        println!("RPC URL: {}", def.rpc_url.bright_green());
+        println!(
+            "Usage: {} sonic-migrate --network {}",
+            "Example:".italic(),
+            name
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_registry_falls_back_to_builtins() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = load_registry(temp_dir.path().to_str().unwrap());
+
+        assert!(registry.contains("testnet"));
+        assert!(registry.contains("mainnet-alpha"));
+        assert_eq!(
+            registry.get("testnet").unwrap().rpc_url,
+            "https://api.testnet.sonic.game"
+        );
+    }
+
+    #[test]
+    fn test_load_registry_merges_and_overrides_user_file() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("sonic-networks.toml"),
+            r#"
+[networks.devnet]
+rpc_url = "https://api.devnet.sonic.game"
+programs_section = "devnet"
+
+[networks.testnet]
+rpc_url = "https://custom.testnet.example.com"
+programs_section = "testnet"
+"#,
+        )
+        .unwrap();
+
+        let registry = load_registry(temp_dir.path().to_str().unwrap());
+
+        // User-defined network is merged in alongside the built-ins.
+        assert!(registry.contains("devnet"));
+        assert_eq!(
+            registry.get("devnet").unwrap().rpc_url,
+            "https://api.devnet.sonic.game"
+        );
+        // User entry for an existing built-in name takes precedence.
+        assert_eq!(
+            registry.get("testnet").unwrap().rpc_url,
+            "https://custom.testnet.example.com"
+        );
+        // Untouched built-ins are still present.
+        assert!(registry.contains("mainnet-alpha"));
+    }
+
+    #[test]
+    fn test_load_registry_ignores_malformed_user_file() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("sonic-networks.toml"),
+            "this is not valid toml [[[",
+        )
+        .unwrap();
+
+        let registry = load_registry(temp_dir.path().to_str().unwrap());
+
+        // Falls back to the built-ins instead of panicking or dropping them.
+        assert!(registry.contains("testnet"));
+        assert!(registry.contains("mainnet-alpha"));
+    }
+}